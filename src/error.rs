@@ -20,4 +20,16 @@ pub enum Error {
         #[from]
         source: std::num::ParseIntError,
     },
+
+    #[error("system call failed: `{0}`")]
+    System(String),
+
+    #[error("float parsing error: `{source}`")]
+    ParseFloat {
+        #[from]
+        source: std::num::ParseFloatError,
+    },
+
+    #[error("insufficient permissions to sample power metrics (re-run with sudo)")]
+    Permission,
 }
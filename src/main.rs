@@ -1,4 +1,5 @@
 mod error;
+mod power;
 mod soc;
 
 fn main() {
@@ -0,0 +1,204 @@
+use crate::error::Error;
+use crate::soc::{RealCommand, Result, SystemCommand};
+
+use std::time::Duration;
+
+// https://github.com/tlkh/asitop/blob/74ebe2cbc23d5b1eec874aebb1b9bacfe0e670cd/asitop/utils.py#L15
+const POWERMETRICS_PATH: &str = "/usr/bin/powermetrics";
+
+/// A single live sample of CPU/GPU/ANE power draw and thermal pressure.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PowerReading {
+    /// Instantaneous CPU package power, in watts
+    pub cpu_power_watts: f32,
+    /// Instantaneous GPU power, in watts
+    pub gpu_power_watts: f32,
+    /// Instantaneous ANE (Neural Engine) power, in watts
+    pub ane_power_watts: f32,
+    /// Current thermal pressure level reported by the kernel
+    pub thermal_pressure: ThermalPressure,
+}
+
+/// Thermal pressure level, as reported by `powermetrics`'s thermal sampler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThermalPressure {
+    Nominal,
+    Moderate,
+    Heavy,
+    Trapping,
+    Sleeping,
+    /// The level was missing or didn't match a known value.
+    Unknown,
+}
+
+/// Samples live power and thermal data via `powermetrics`. Requires elevated
+/// privileges; a non-root invocation surfaces as [`Error::Permission`].
+pub struct PowerMonitor {
+    sample_interval: Duration,
+}
+
+impl PowerMonitor {
+    /// Creates a monitor that takes a single ~1s sample per call.
+    pub fn new() -> Self {
+        Self::with_sample_interval(Duration::from_millis(1000))
+    }
+
+    /// Creates a monitor that samples over the given interval.
+    pub fn with_sample_interval(sample_interval: Duration) -> Self {
+        Self { sample_interval }
+    }
+
+    /// Takes one power/thermal sample.
+    pub fn sample(&self) -> Result<PowerReading> {
+        self.sample_with(&RealCommand)
+    }
+
+    fn sample_with(&self, cmd: &impl SystemCommand) -> Result<PowerReading> {
+        let interval_ms = self.sample_interval.as_millis().to_string();
+        let args = &[
+            "--samplers",
+            "cpu_power,gpu_power,thermal",
+            "-i",
+            interval_ms.as_str(),
+            "-n",
+            "1",
+        ];
+
+        let output = cmd.execute(POWERMETRICS_PATH, args)?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr).to_lowercase();
+            if stderr.contains("permission") || stderr.contains("superuser") || stderr.contains("root") {
+                return Err(Error::Permission);
+            }
+            return Err(Error::System(format!(
+                "powermetrics exited with {}",
+                output.status
+            )));
+        }
+
+        let buffer = String::from_utf8(output.stdout)?;
+
+        Ok(PowerReading {
+            cpu_power_watts: parse_watts(&buffer, "CPU Power")?,
+            gpu_power_watts: parse_watts(&buffer, "GPU Power")?,
+            ane_power_watts: parse_watts(&buffer, "ANE Power")?,
+            thermal_pressure: parse_thermal_pressure(&buffer),
+        })
+    }
+}
+
+impl Default for PowerMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parses a `"<Label> Power: <n> mW"` line into watts.
+fn parse_watts(buffer: &str, label: &str) -> Result<f32> {
+    let line = buffer
+        .lines()
+        .find(|line| line.trim_start().starts_with(label))
+        .ok_or_else(|| Error::Parse(format!("missing `{label}` in powermetrics output")))?;
+
+    let milliwatts = line
+        .split(':')
+        .nth(1)
+        .and_then(|value| value.trim().split_whitespace().next())
+        .ok_or_else(|| Error::Parse(line.to_string()))?
+        .parse::<f32>()?;
+
+    Ok(milliwatts / 1000.0)
+}
+
+/// Parses the `"Current pressure level: <level>"` line from the thermal sampler.
+fn parse_thermal_pressure(buffer: &str) -> ThermalPressure {
+    buffer
+        .lines()
+        .find(|line| line.trim_start().starts_with("Current pressure level"))
+        .and_then(|line| line.split(':').nth(1))
+        .map(|level| match level.trim() {
+            "Nominal" => ThermalPressure::Nominal,
+            "Moderate" => ThermalPressure::Moderate,
+            "Heavy" => ThermalPressure::Heavy,
+            "Trapping" => ThermalPressure::Trapping,
+            "Sleeping" => ThermalPressure::Sleeping,
+            _ => ThermalPressure::Unknown,
+        })
+        .unwrap_or(ThermalPressure::Unknown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::process::ExitStatusExt;
+    use std::process::Output;
+
+    struct MockCommand {
+        stdout: Vec<u8>,
+        stderr: Vec<u8>,
+        exit_code: i32,
+    }
+
+    impl MockCommand {
+        fn ok(stdout: &str) -> Self {
+            Self {
+                stdout: stdout.as_bytes().to_vec(),
+                stderr: Vec::new(),
+                exit_code: 0,
+            }
+        }
+
+        fn failure(stderr: &str) -> Self {
+            Self {
+                stdout: Vec::new(),
+                stderr: stderr.as_bytes().to_vec(),
+                exit_code: 1,
+            }
+        }
+    }
+
+    impl SystemCommand for MockCommand {
+        fn execute(&self, _binary: &str, _args: &[&str]) -> Result<Output> {
+            Ok(Output {
+                status: std::process::ExitStatus::from_raw(self.exit_code << 8),
+                stdout: self.stdout.clone(),
+                stderr: self.stderr.clone(),
+            })
+        }
+    }
+
+    #[test]
+    fn test_sample_parses_power_and_thermal() {
+        let mock_output = "CPU Power: 1234 mW\nGPU Power: 567 mW\nANE Power: 0 mW\nCurrent pressure level: Nominal\n";
+        let cmd = MockCommand::ok(mock_output);
+
+        let monitor = PowerMonitor::new();
+        let reading = monitor.sample_with(&cmd).unwrap();
+
+        assert_eq!(reading.cpu_power_watts, 1.234);
+        assert_eq!(reading.gpu_power_watts, 0.567);
+        assert_eq!(reading.ane_power_watts, 0.0);
+        assert_eq!(reading.thermal_pressure, ThermalPressure::Nominal);
+    }
+
+    #[test]
+    fn test_sample_permission_denied() {
+        let cmd = MockCommand::failure("powermetrics must be invoked as the superuser\n");
+
+        let monitor = PowerMonitor::new();
+        let result = monitor.sample_with(&cmd);
+
+        assert!(matches!(result, Err(Error::Permission)));
+    }
+
+    #[test]
+    fn test_sample_missing_field() {
+        let cmd = MockCommand::ok("GPU Power: 567 mW\n");
+
+        let monitor = PowerMonitor::new();
+        let result = monitor.sample_with(&cmd);
+
+        assert!(matches!(result, Err(Error::Parse(_))));
+    }
+}